@@ -0,0 +1,115 @@
+// Native rounded-corner border for transparent macOS windows.
+//
+// `transparent(true)` plus the manual background-color/shadow block leaves
+// an ugly dark edge where the window's square layer meets its rounded
+// corners. Giving the content view's own `CALayer` a matching corner radius
+// and a thin border color fixes that cleanly, without drawing anything in
+// the webview itself. No-op on every other platform.
+
+use std::sync::Mutex;
+
+use tauri::WebviewWindow;
+
+#[derive(Clone, Copy)]
+pub struct BorderConfig {
+    pub color: [f64; 4],
+    pub width: f64,
+    pub radius: f64,
+}
+
+impl Default for BorderConfig {
+    fn default() -> Self {
+        Self {
+            color: [0.0, 0.0, 0.0, 0.08],
+            width: 1.0,
+            radius: 12.0,
+        }
+    }
+}
+
+pub struct WindowBorderState(pub Mutex<BorderConfig>);
+
+impl Default for WindowBorderState {
+    fn default() -> Self {
+        Self(Mutex::new(BorderConfig::default()))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::BorderConfig;
+    use cocoa::appkit::NSView;
+    use cocoa::base::{id, YES};
+    use objc::{class, msg_send, sel, sel_impl};
+    use tauri::WebviewWindow;
+
+    /// Apply `config` to the window's content-view layer.
+    pub fn apply(window: &WebviewWindow, config: BorderConfig) {
+        let Ok(ns_window) = window.ns_window().map(|w| w as id) else {
+            return;
+        };
+
+        unsafe {
+            let content_view: id = msg_send![ns_window, contentView];
+            if content_view.is_null() {
+                return;
+            }
+            content_view.setWantsLayer(YES);
+            let layer: id = msg_send![content_view, layer];
+            if layer.is_null() {
+                return;
+            }
+
+            let [r, g, b, a] = config.color;
+            let cg_color_class = class!(NSColor);
+            let ns_color: id = msg_send![cg_color_class, colorWithRed:r green:g blue:b alpha:a];
+            let cg_color: id = msg_send![ns_color, CGColor];
+
+            let _: () = msg_send![layer, setBorderColor: cg_color];
+            let _: () = msg_send![layer, setBorderWidth: config.width];
+            let _: () = msg_send![layer, setCornerRadius: config.radius];
+            let _: () = msg_send![layer, setMasksToBounds: YES];
+        }
+    }
+}
+
+/// Apply a sensible default border; called once from `setup`.
+pub fn apply_default(window: &WebviewWindow) {
+    #[cfg(target_os = "macos")]
+    macos::apply(window, BorderConfig::default());
+    #[cfg(not(target_os = "macos"))]
+    let _ = window;
+}
+
+/// Re-apply the last-configured border; called from the resize handler so
+/// the border layer tracks the window's new bounds.
+pub fn reapply(window: &WebviewWindow, config: BorderConfig) {
+    #[cfg(target_os = "macos")]
+    macos::apply(window, config);
+    #[cfg(not(target_os = "macos"))]
+    let _ = (window, config);
+}
+
+#[tauri::command]
+pub fn set_window_border(
+    window: WebviewWindow,
+    state: tauri::State<WindowBorderState>,
+    color: [f64; 4],
+    width: f64,
+    radius: f64,
+) -> Result<(), String> {
+    let config = BorderConfig { color, width, radius };
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (&window, &state, config);
+        return Ok(());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        *state.0.lock().map_err(|e| e.to_string())? = config;
+        macos::apply(&window, config);
+        Ok(())
+    }
+}
@@ -1,45 +1,27 @@
 // Prevents console window on Windows in release builds
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::{Manager, TitleBarStyle, WebviewUrl, WebviewWindowBuilder, window::{Effect, EffectState, EffectsBuilder}};
-use tauri_plugin_updater::UpdaterExt;
+use tauri::{Manager, TitleBarStyle, WebviewUrl, WebviewWindowBuilder};
 
-#[tauri::command]
-fn check_for_updates(app: tauri::AppHandle) {
-    tauri::async_runtime::spawn(async move {
-        match app.updater_builder().build() {
-            Ok(updater) => {
-                if let Ok(Some(update)) = updater.check().await {
-                    let mut downloaded = 0;
-                    
-                    // Download and install the update
-                    update
-                        .download_and_install(
-                            |chunk_length, content_length| {
-                                downloaded += chunk_length;
-                                println!("Downloaded {} of {:?}", downloaded, content_length);
-                            },
-                            || {
-                                println!("Download finished");
-                            },
-                        )
-                        .await
-                        .ok();
-                }
-            }
-            Err(e) => {
-                eprintln!("Failed to build updater: {}", e);
-            }
-        }
-    });
-}
+mod border;
+mod material;
+mod theme;
+mod titlebar;
+mod updater;
 
 fn main() {
     tauri::Builder::default()
+        .manage(titlebar::TrafficLightInset::default())
+        .manage(titlebar::MaximizeHitbox::default())
+        .manage(border::WindowBorderState::default())
+        .manage(updater::UpdateState::default())
+        .manage(theme::ThemeState::default())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .setup(|app| {
-            // Create main window programmatically for proper macOS transparency
+            // Create main window programmatically for proper macOS transparency.
+            // Frameless so we own the chrome; `titlebar::setup` below re-adds
+            // native traffic lights / Snap Layouts support on top of that.
             let win_builder = WebviewWindowBuilder::new(app, "main", WebviewUrl::default())
                 .title("")
                 .inner_size(1280.0, 800.0)
@@ -47,7 +29,7 @@ fn main() {
                 .resizable(true)
                 .fullscreen(false)
                 .transparent(true)
-                .decorations(true)
+                .decorations(false)
                 .center()
                 .visible(false)
                 .devtools(true);
@@ -58,71 +40,82 @@ fn main() {
 
             let window = win_builder.build().unwrap();
 
-            // Set custom background color for macOS transparent titlebar
+            titlebar::setup(&window);
+
+            // Ensure window has shadow (background color is handled by `theme`,
+            // which also applies the right palette for the current appearance)
             #[cfg(target_os = "macos")]
             {
-                use cocoa::appkit::{NSColor, NSWindow};
-                use cocoa::base::{id, nil};
+                use cocoa::base::id;
                 use objc::{msg_send, sel, sel_impl};
 
                 let ns_window = window.ns_window().unwrap() as id;
                 unsafe {
-                    // Set background color to match your cream color #f6f5f3
-                    let bg_color = NSColor::colorWithRed_green_blue_alpha_(
-                        nil,
-                        246.0 / 255.0,  // #F6F5F3 red component
-                        245.0 / 255.0,  // #F6F5F3 green component
-                        243.0 / 255.0,  // #F6F5F3 blue component
-                        1.0,            // Fully opaque
-                    );
-                    ns_window.setBackgroundColor_(bg_color);
-
-                    // Ensure window has shadow
                     let _: () = msg_send![ns_window, setHasShadow: true];
                 }
             }
 
+            // Native rounded-corner border so the cream background meets the
+            // rounded corners cleanly instead of showing a dark edge
+            border::apply_default(&window);
+            {
+                let window_for_border = window.clone();
+                window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::Resized(_) = event {
+                        let config = *window_for_border
+                            .state::<border::WindowBorderState>()
+                            .0
+                            .lock()
+                            .unwrap();
+                        border::reapply(&window_for_border, config);
+                    }
+                });
+            }
+
             // Show window after setup to avoid flash
             window.show().unwrap();
 
-            // Apply native blur effects to the window
-            #[cfg(target_os = "windows")]
+            // Apply the background color and vibrancy material for the current
+            // system appearance, then keep them in sync as it changes
+            theme::apply_initial(&app.handle(), &window);
             {
-                if let Err(e) = window.set_effects(
-                    EffectsBuilder::new()
-                        .effect(Effect::Mica)
-                        .state(EffectState::Active)
-                        .build(),
-                ) {
-                    eprintln!("Failed to set window effects: {}", e);
-                }
+                let app_for_theme = app.handle().clone();
+                let window_for_theme = window.clone();
+                window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::ThemeChanged(new_theme) = event {
+                        theme::on_system_theme_changed(&app_for_theme, &window_for_theme, *new_theme);
+                    }
+                });
             }
 
-            #[cfg(target_os = "macos")]
-            {
-                if let Err(e) = window.set_effects(
-                    EffectsBuilder::new()
-                        .effect(Effect::Tooltip)
-                        .state(EffectState::Active)
-                        .build(),
-                ) {
-                    eprintln!("Failed to set window effects: {}", e);
-                }
-            }
-            
-            // Check for updates on startup (silent check)
+            // Check for updates on startup. This only emits `update-available`;
+            // it's up to the frontend to call `download_and_install_update`.
             #[cfg(not(debug_assertions))]
             {
                 let app_handle = app.handle().clone();
                 tauri::async_runtime::spawn(async move {
-                    std::thread::sleep(std::time::Duration::from_secs(5));
-                    check_for_updates(app_handle);
+                    // Avoid depending on tokio directly for a one-off delay;
+                    // `async_runtime` is already pulled in via `tauri`.
+                    let _ = tauri::async_runtime::spawn_blocking(|| {
+                        std::thread::sleep(std::time::Duration::from_secs(5));
+                    })
+                    .await;
+                    updater::check(&app_handle).await;
                 });
             }
             
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![check_for_updates])
+        .invoke_handler(tauri::generate_handler![
+            material::set_window_material,
+            titlebar::reposition_traffic_lights,
+            titlebar::set_maximize_hitbox,
+            border::set_window_border,
+            updater::get_update_status,
+            updater::download_and_install_update,
+            updater::restart_app,
+            theme::set_theme_colors
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
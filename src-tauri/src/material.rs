@@ -0,0 +1,172 @@
+// Window vibrancy/blur material subsystem.
+//
+// Maps a frontend-facing material name onto the platform-specific `Effect`
+// variant so the window can be re-themed at runtime instead of being stuck
+// with the one effect picked in `setup`. The chosen material is persisted to
+// disk so it's re-applied on the next launch.
+
+use std::fs;
+
+use tauri::{
+    window::{Effect, EffectState, EffectsBuilder},
+    AppHandle, Manager, WebviewWindow,
+};
+
+#[cfg(target_os = "windows")]
+use tauri::window::Color;
+
+const MATERIAL_FILE: &str = "window-material.txt";
+const DEFAULT_MATERIAL: &str = if cfg!(target_os = "windows") {
+    "mica"
+} else {
+    "tooltip"
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Material {
+    // macOS (NSVisualEffectMaterial)
+    Sidebar,
+    HudWindow,
+    FullScreenUi,
+    Popover,
+    UnderWindowBackground,
+    Tooltip,
+    // Windows
+    Blur,
+    Acrylic,
+    Mica,
+    Tabbed,
+}
+
+impl Material {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "sidebar" => Ok(Material::Sidebar),
+            "hud-window" => Ok(Material::HudWindow),
+            "full-screen-ui" => Ok(Material::FullScreenUi),
+            "popover" => Ok(Material::Popover),
+            "under-window-background" => Ok(Material::UnderWindowBackground),
+            "tooltip" => Ok(Material::Tooltip),
+            "blur" => Ok(Material::Blur),
+            "acrylic" => Ok(Material::Acrylic),
+            "mica" => Ok(Material::Mica),
+            "tabbed" => Ok(Material::Tabbed),
+            other => Err(format!("unknown window material: {other}")),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Material::Sidebar => "sidebar",
+            Material::HudWindow => "hud-window",
+            Material::FullScreenUi => "full-screen-ui",
+            Material::Popover => "popover",
+            Material::UnderWindowBackground => "under-window-background",
+            Material::Tooltip => "tooltip",
+            Material::Blur => "blur",
+            Material::Acrylic => "acrylic",
+            Material::Mica => "mica",
+            Material::Tabbed => "tabbed",
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn effect(self) -> Result<Effect, String> {
+        match self {
+            Material::Sidebar => Ok(Effect::Sidebar),
+            Material::HudWindow => Ok(Effect::HudWindow),
+            Material::FullScreenUi => Ok(Effect::FullScreenUI),
+            Material::Popover => Ok(Effect::Popover),
+            Material::UnderWindowBackground => Ok(Effect::UnderWindowBackground),
+            Material::Tooltip => Ok(Effect::Tooltip),
+            other => Err(format!("{} is a Windows-only material", other.as_str())),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn effect(self) -> Result<Effect, String> {
+        match self {
+            Material::Blur => Ok(Effect::Blur),
+            Material::Acrylic => Ok(Effect::Acrylic),
+            Material::Mica => Ok(Effect::Mica),
+            Material::Tabbed => Ok(Effect::Tabbed),
+            other => Err(format!("{} is a macOS-only material", other.as_str())),
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn effect(self) -> Result<Effect, String> {
+        Err("window materials are not supported on this platform".into())
+    }
+}
+
+/// Build and apply an `EffectsBuilder` for `name`, optionally tinted (Windows
+/// acrylic/blur only). No-ops on Linux.
+pub fn apply(window: &WebviewWindow, name: &str, tint: Option<(u8, u8, u8, u8)>) -> Result<(), String> {
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (window, name, tint);
+        return Ok(());
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    {
+        let material = Material::parse(name)?;
+        let effect = material.effect()?;
+
+        let mut builder = EffectsBuilder::new().effect(effect).state(EffectState::Active);
+
+        #[cfg(target_os = "windows")]
+        if let Some((r, g, b, a)) = tint {
+            builder = builder.color(Color(r, g, b, a));
+        }
+        #[cfg(target_os = "macos")]
+        let _ = tint;
+
+        window
+            .set_effects(builder.build())
+            .map_err(|e| format!("failed to set window effects: {e}"))
+    }
+}
+
+fn material_file(app: &AppHandle) -> Option<std::path::PathBuf> {
+    app.path().app_config_dir().ok().map(|dir| dir.join(MATERIAL_FILE))
+}
+
+/// Persist the chosen material so it can be re-applied on the next launch.
+fn persist(app: &AppHandle, name: &str) {
+    let Some(path) = material_file(app) else { return };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(path, name);
+}
+
+/// Load the last persisted material, falling back to the platform default.
+pub fn load_persisted(app: &AppHandle) -> String {
+    material_file(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| Material::parse(s).is_ok())
+        .unwrap_or_else(|| DEFAULT_MATERIAL.to_string())
+}
+
+/// Apply the persisted material (or platform default) to `window` during setup.
+pub fn apply_persisted(app: &AppHandle, window: &WebviewWindow) {
+    let name = load_persisted(app);
+    if let Err(e) = apply(window, &name, None) {
+        eprintln!("failed to apply persisted window material: {e}");
+    }
+}
+
+#[tauri::command]
+pub fn set_window_material(
+    app: AppHandle,
+    window: WebviewWindow,
+    name: String,
+    tint: Option<(u8, u8, u8, u8)>,
+) -> Result<(), String> {
+    apply(&window, &name, tint)?;
+    persist(&app, &name);
+    Ok(())
+}
@@ -0,0 +1,158 @@
+// System appearance (dark/light) awareness.
+//
+// The window background and vibrancy material used to be set once in
+// `setup` and never touched again. This watches Tauri's `ThemeChanged`
+// window event and re-drives both the background color and the effect so
+// the transparent titlebar and window body stay consistent when the OS
+// switches appearance, without a restart.
+
+use std::fs;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, Theme, WebviewWindow};
+
+use crate::material;
+
+const THEME_COLORS_FILE: &str = "theme-colors.txt";
+
+// Cream, matching the previous hardcoded #f6f5f3.
+const DEFAULT_LIGHT: [f64; 4] = [246.0 / 255.0, 245.0 / 255.0, 243.0 / 255.0, 1.0];
+// A dark neutral that pairs with the vibrancy materials in dark mode.
+const DEFAULT_DARK: [f64; 4] = [30.0 / 255.0, 30.0 / 255.0, 32.0 / 255.0, 1.0];
+
+#[derive(Clone, Copy)]
+pub struct ThemeColors {
+    pub light: [f64; 4],
+    pub dark: [f64; 4],
+    // Vibrancy material to use for each appearance. Not user-configurable
+    // through `set_theme_colors` (which only takes colors, per the request),
+    // but kept alongside the palette since the two are applied together.
+    light_material: &'static str,
+    dark_material: &'static str,
+}
+
+impl Default for ThemeColors {
+    fn default() -> Self {
+        Self {
+            light: DEFAULT_LIGHT,
+            dark: DEFAULT_DARK,
+            light_material: if cfg!(target_os = "windows") { "mica" } else { "tooltip" },
+            dark_material: if cfg!(target_os = "windows") { "acrylic" } else { "hud-window" },
+        }
+    }
+}
+
+pub struct ThemeState {
+    colors: Mutex<ThemeColors>,
+    current: Mutex<Theme>,
+}
+
+impl Default for ThemeState {
+    fn default() -> Self {
+        Self {
+            colors: Mutex::new(ThemeColors::default()),
+            current: Mutex::new(Theme::Light),
+        }
+    }
+}
+
+fn colors_file(app: &AppHandle) -> Option<std::path::PathBuf> {
+    app.path().app_config_dir().ok().map(|dir| dir.join(THEME_COLORS_FILE))
+}
+
+fn persist(app: &AppHandle, colors: ThemeColors) {
+    let Some(path) = colors_file(app) else { return };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let line = |c: [f64; 4]| format!("{},{},{},{}", c[0], c[1], c[2], c[3]);
+    let _ = fs::write(path, format!("{}\n{}", line(colors.light), line(colors.dark)));
+}
+
+fn parse_color(s: &str) -> Option<[f64; 4]> {
+    let mut parts = s.split(',').map(|p| p.trim().parse::<f64>());
+    Some([parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?])
+}
+
+fn load_persisted(app: &AppHandle) -> ThemeColors {
+    let Some(contents) = colors_file(app).and_then(|p| fs::read_to_string(p).ok()) else {
+        return ThemeColors::default();
+    };
+    let mut lines = contents.lines();
+    match (lines.next().and_then(parse_color), lines.next().and_then(parse_color)) {
+        (Some(light), Some(dark)) => ThemeColors { light, dark, ..ThemeColors::default() },
+        _ => ThemeColors::default(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn set_background(window: &WebviewWindow, [r, g, b, a]: [f64; 4]) {
+    use cocoa::appkit::{NSColor, NSWindow};
+    use cocoa::base::{id, nil};
+
+    let Ok(ns_window) = window.ns_window().map(|w| w as id) else {
+        return;
+    };
+    unsafe {
+        let bg_color = NSColor::colorWithRed_green_blue_alpha_(nil, r, g, b, a);
+        ns_window.setBackgroundColor_(bg_color);
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_background(_window: &WebviewWindow, _color: [f64; 4]) {}
+
+/// Apply just the background color for `theme`, without touching the
+/// vibrancy material. Used where the material should be left alone (startup,
+/// and a manual color-only palette update).
+fn apply_color(app: &AppHandle, window: &WebviewWindow, theme: Theme) {
+    *app.state::<ThemeState>().current.lock().unwrap() = theme;
+    let colors = *app.state::<ThemeState>().colors.lock().unwrap();
+    let color = match theme {
+        Theme::Dark => colors.dark,
+        _ => colors.light,
+    };
+    set_background(window, color);
+}
+
+/// Read the window's current theme and apply it; called once from `setup`
+/// in place of the old fixed cream background. The vibrancy material is
+/// whatever `set_window_material` last persisted (or the platform default),
+/// matching `material`'s "re-applied on next launch" contract.
+pub fn apply_initial(app: &AppHandle, window: &WebviewWindow) {
+    *app.state::<ThemeState>().colors.lock().unwrap() = load_persisted(app);
+    let theme = window.theme().unwrap_or(Theme::Light);
+    apply_color(app, window, theme);
+    material::apply_persisted(app, window);
+}
+
+/// Re-drive the background color *and* pick a theme-appropriate vibrancy
+/// material; called when the OS appearance actually changes so dark mode
+/// gets a darker/more opaque material instead of the light-mode one.
+///
+/// This applies the material live only — it does not persist it. Persistence
+/// is `material`'s job and belongs to the user's explicit `set_window_material`
+/// choice (chunk0-1); letting an automatic appearance switch overwrite that
+/// would silently lose the user's pick the next time the OS theme flips.
+pub fn on_system_theme_changed(app: &AppHandle, window: &WebviewWindow, theme: Theme) {
+    apply_color(app, window, theme);
+
+    let colors = *app.state::<ThemeState>().colors.lock().unwrap();
+    let material_name = match theme {
+        Theme::Dark => colors.dark_material,
+        _ => colors.light_material,
+    };
+    if let Err(e) = material::apply(window, material_name, None) {
+        eprintln!("failed to apply theme vibrancy material: {e}");
+    }
+}
+
+#[tauri::command]
+pub fn set_theme_colors(app: AppHandle, window: WebviewWindow, light: [f64; 4], dark: [f64; 4]) {
+    let colors = ThemeColors { light, dark, ..ThemeColors::default() };
+    *app.state::<ThemeState>().colors.lock().unwrap() = colors;
+    persist(&app, colors);
+
+    let current = *app.state::<ThemeState>().current.lock().unwrap();
+    apply_color(&app, &window, current);
+}
@@ -0,0 +1,240 @@
+// Frameless window with native traffic lights / Snap Layouts preserved.
+//
+// The window is built with `decorations(false)` so we fully own the chrome,
+// but on macOS we still want the native traffic-light buttons (for the
+// gray-when-unfocused/hover behavior that's painful to reimplement), just
+// repositioned to line up with a custom toolbar. On Windows we keep a sliver
+// of native caption plus a `WM_NCHITTEST` hook over the frontend's custom
+// maximize button, so Snap Layouts' hover flyout still works.
+//
+// The frontend marks its own draggable toolbar with the
+// `data-tauri-drag-region` attribute (handled natively by Tauri), so there's
+// no Rust-side drag command needed here. It does need to call
+// `set_maximize_hitbox` with its maximize button's rect, though, since that
+// one isn't something Tauri tracks for us.
+
+use std::sync::Mutex;
+
+use tauri::WebviewWindow;
+
+/// Last inset applied to the traffic lights, re-applied on resize/focus.
+pub struct TrafficLightInset(Mutex<(f64, f64)>);
+
+impl Default for TrafficLightInset {
+    fn default() -> Self {
+        Self(Mutex::new((12.0, 12.0)))
+    }
+}
+
+/// Custom maximize-button hit region, in physical client pixels `(x, y, width,
+/// height)`; `set_maximize_hitbox` takes logical pixels and scales them by the
+/// window's scale factor at the time of the call. On Windows this is where we
+/// answer `WM_NCHITTEST` with
+/// `HTMAXBUTTON` so hovering it still raises the native Snap Layouts flyout,
+/// even though the window itself has no native maximize button.
+pub struct MaximizeHitbox(Mutex<(f64, f64, f64, f64)>);
+
+impl Default for MaximizeHitbox {
+    fn default() -> Self {
+        Self(Mutex::new((0.0, 0.0, 0.0, 0.0)))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::TrafficLightInset;
+    use cocoa::appkit::{NSWindow, NSWindowButton, NSWindowStyleMask};
+    use cocoa::base::id;
+    use cocoa::foundation::{NSPoint, NSRect};
+    use objc::{msg_send, sel, sel_impl};
+    use tauri::{Manager, WebviewWindow};
+
+    /// Re-enable the native titlebar buttons on a window built with
+    /// `decorations(false)`, keeping the transparent/hidden title chrome.
+    pub fn install(window: &WebviewWindow) {
+        let ns_window = match window.ns_window() {
+            Ok(w) => w as id,
+            Err(_) => return,
+        };
+
+        unsafe {
+            let mut mask = ns_window.styleMask();
+            mask |= NSWindowStyleMask::NSTitledWindowMask
+                | NSWindowStyleMask::NSClosableWindowMask
+                | NSWindowStyleMask::NSMiniaturizableWindowMask
+                | NSWindowStyleMask::NSResizableWindowMask
+                | NSWindowStyleMask::NSFullSizeContentViewWindowMask;
+            ns_window.setStyleMask_(mask);
+
+            ns_window.setTitlebarAppearsTransparent_(cocoa::base::YES);
+            let _: () = msg_send![ns_window, setTitleVisibility: 1]; // NSWindowTitleHidden
+        }
+
+        let state = window.state::<TrafficLightInset>();
+        let (x, y) = *state.0.lock().unwrap();
+        apply_inset(window, x, y);
+    }
+
+    /// Shift the traffic-light button cluster to `(x, y)` from the window's
+    /// top-left, by reading each button's superview frame and moving it.
+    pub fn apply_inset(window: &WebviewWindow, x: f64, y: f64) {
+        let Ok(ns_window) = window.ns_window().map(|w| w as id) else {
+            return;
+        };
+
+        unsafe {
+            // The three buttons live in a shared superview; move it once
+            // using the close button as the anchor.
+            let close_button: id = ns_window.standardWindowButton_(NSWindowButton::NSWindowCloseButton);
+            if close_button.is_null() {
+                return;
+            }
+            let title_bar_view: id = msg_send![close_button, superview];
+            if title_bar_view.is_null() {
+                return;
+            }
+
+            let mut frame: NSRect = msg_send![title_bar_view, frame];
+            let window_frame: NSRect = NSWindow::frame(ns_window);
+            frame.origin = NSPoint::new(x, window_frame.size.height - frame.size.height - y);
+            let _: () = msg_send![title_bar_view, setFrameOrigin: frame.origin];
+        }
+    }
+}
+
+/// Set up whatever's needed to keep native window-control behavior on a
+/// frameless window: traffic-light overlay on macOS, Snap Layouts caption
+/// region on Windows. No-op elsewhere.
+pub fn setup(window: &WebviewWindow) {
+    #[cfg(target_os = "macos")]
+    macos::install(window);
+
+    #[cfg(target_os = "windows")]
+    windows::preserve_snap_layouts(window);
+
+    let window_for_events = window.clone();
+    window.on_window_event(move |event| match event {
+        tauri::WindowEvent::Resized(_) | tauri::WindowEvent::Focused(_) => {
+            #[cfg(target_os = "macos")]
+            {
+                let state = window_for_events.state::<TrafficLightInset>();
+                let (x, y) = *state.0.lock().unwrap();
+                macos::apply_inset(&window_for_events, x, y);
+            }
+        }
+        _ => {}
+    });
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::MaximizeHitbox;
+    use tauri::{Manager, WebviewWindow};
+    use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM};
+    use windows_sys::Win32::Graphics::Dwm::{DwmExtendFrameIntoClientArea, MARGINS};
+    use windows_sys::Win32::Graphics::Gdi::ScreenToClient;
+    use windows_sys::Win32::UI::Shell::{DefSubclassProc, SetWindowSubclass};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{HTMAXBUTTON, WM_NCHITTEST};
+
+    /// `WM_NCHITTEST` handler installed on the window: when the cursor is
+    /// over the frontend-reported maximize-button region, answer
+    /// `HTMAXBUTTON` instead of falling through to the default (no native
+    /// maximize button exists on a `decorations(false)` window), which is
+    /// what actually makes Windows show the Snap Layouts flyout on hover.
+    unsafe extern "system" fn hit_test_subclass(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _uidsubclass: usize,
+        dwrefdata: usize,
+    ) -> LRESULT {
+        if msg == WM_NCHITTEST {
+            let hitbox = &*(dwrefdata as *const MaximizeHitbox);
+            let (x, y, width, height) = *hitbox.0.lock().unwrap();
+            if width > 0.0 && height > 0.0 {
+                let lparam32 = lparam as i32;
+                let mut point = POINT {
+                    x: (lparam32 & 0xffff) as i16 as i32,
+                    y: ((lparam32 >> 16) & 0xffff) as i16 as i32,
+                };
+                ScreenToClient(hwnd, &mut point);
+                let (px, py) = (point.x as f64, point.y as f64);
+                if px >= x && px <= x + width && py >= y && py <= y + height {
+                    return HTMAXBUTTON as LRESULT;
+                }
+            }
+        }
+        DefSubclassProc(hwnd, msg, wparam, lparam)
+    }
+
+    /// Extend a thin strip of native caption into the client area and hook
+    /// `WM_NCHITTEST` so hovering the frontend's custom maximize button shows
+    /// the native Snap Layouts flyout, even though we draw our own titlebar.
+    pub fn preserve_snap_layouts(window: &WebviewWindow) {
+        let Ok(hwnd) = window.hwnd() else { return };
+        let margins = MARGINS {
+            cxLeftWidth: 0,
+            cxRightWidth: 0,
+            cyTopHeight: 1,
+            cyBottomHeight: 0,
+        };
+
+        // The hitbox is already managed app state; SetWindowSubclass just
+        // needs a stable pointer to it for the lifetime of the window.
+        let hitbox_ptr = window.state::<MaximizeHitbox>().inner() as *const MaximizeHitbox as usize;
+
+        unsafe {
+            let _ = DwmExtendFrameIntoClientArea(hwnd.0 as HWND, &margins);
+            SetWindowSubclass(hwnd.0 as HWND, Some(hit_test_subclass), 1, hitbox_ptr);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn reposition_traffic_lights(
+    window: WebviewWindow,
+    state: tauri::State<TrafficLightInset>,
+    x: f64,
+    y: f64,
+) -> Result<(), String> {
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (&window, &state, x, y);
+        return Ok(());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        *state.0.lock().map_err(|e| e.to_string())? = (x, y);
+        macos::apply_inset(&window, x, y);
+        Ok(())
+    }
+}
+
+/// Tell the Windows `WM_NCHITTEST` hook where the frontend's custom maximize
+/// button lives, so hovering it raises the native Snap Layouts flyout.
+/// No-op elsewhere.
+#[tauri::command]
+pub fn set_maximize_hitbox(
+    window: WebviewWindow,
+    state: tauri::State<MaximizeHitbox>,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) -> Result<(), String> {
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (&window, &state, x, y, width, height);
+        return Ok(());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let scale = window.scale_factor().map_err(|e| e.to_string())?;
+        *state.0.lock().map_err(|e| e.to_string())? =
+            (x * scale, y * scale, width * scale, height * scale);
+        Ok(())
+    }
+}
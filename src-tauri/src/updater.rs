@@ -0,0 +1,125 @@
+// Event-driven updater.
+//
+// Replaces the old fire-and-forget `check_for_updates` (background task that
+// only `println!`d progress and silently installed) with a small queryable
+// state machine: the startup check just emits `update-available` and lets
+// the frontend decide when to download, instead of auto-installing behind
+// the user's back.
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+#[derive(Clone, Serialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum UpdateStatus {
+    Idle,
+    Available { version: String, notes: Option<String> },
+    Downloading { downloaded: usize, content_length: Option<u64> },
+    Ready,
+    Error { message: String },
+}
+
+#[derive(Default)]
+pub struct UpdateState {
+    status: Mutex<UpdateStatus>,
+    pending: Mutex<Option<Update>>,
+}
+
+impl Default for UpdateStatus {
+    fn default() -> Self {
+        UpdateStatus::Idle
+    }
+}
+
+fn set_status(app: &AppHandle, status: UpdateStatus) {
+    let state = app.state::<UpdateState>();
+    *state.status.lock().unwrap() = status.clone();
+    let event = match &status {
+        UpdateStatus::Idle => return,
+        UpdateStatus::Available { .. } => "update-available",
+        UpdateStatus::Downloading { .. } => "update-progress",
+        UpdateStatus::Ready => "update-ready",
+        UpdateStatus::Error { .. } => "update-error",
+    };
+    let _ = app.emit(event, status);
+}
+
+/// Check for an update and, if one exists, emit `update-available` and stash
+/// it so a later `download_and_install_update()` call doesn't need to
+/// re-check. Does not download.
+pub async fn check(app: &AppHandle) {
+    let updater = match app.updater_builder().build() {
+        Ok(updater) => updater,
+        Err(e) => {
+            set_status(app, UpdateStatus::Error { message: format!("failed to build updater: {e}") });
+            return;
+        }
+    };
+
+    match updater.check().await {
+        Ok(Some(update)) => {
+            let version = update.version.clone();
+            let notes = update.body.clone();
+            *app.state::<UpdateState>().pending.lock().unwrap() = Some(update);
+            set_status(app, UpdateStatus::Available { version, notes });
+        }
+        Ok(None) => {}
+        Err(e) => {
+            set_status(app, UpdateStatus::Error { message: format!("update check failed: {e}") });
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_update_status(app: AppHandle) -> UpdateStatus {
+    app.state::<UpdateState>().status.lock().unwrap().clone()
+}
+
+/// Download and install the previously-announced update. This is the
+/// user-consent step: nothing downloads until the frontend calls this.
+#[tauri::command]
+pub async fn download_and_install_update(app: AppHandle) -> Result<(), String> {
+    let update = app
+        .state::<UpdateState>()
+        .pending
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("no update available to install")?;
+
+    let app_for_progress = app.clone();
+    let mut downloaded = 0usize;
+
+    let result = update
+        .download_and_install(
+            move |chunk_length, content_length| {
+                downloaded += chunk_length;
+                set_status(
+                    &app_for_progress,
+                    UpdateStatus::Downloading { downloaded, content_length },
+                );
+            },
+            || {},
+        )
+        .await;
+
+    match result {
+        Ok(()) => {
+            set_status(&app, UpdateStatus::Ready);
+            Ok(())
+        }
+        Err(e) => {
+            let message = format!("failed to download/install update: {e}");
+            set_status(&app, UpdateStatus::Error { message: message.clone() });
+            Err(message)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn restart_app(app: AppHandle) {
+    app.restart();
+}